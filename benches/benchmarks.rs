@@ -24,7 +24,7 @@ fn bench_insert(c: &mut Criterion) {
         group.throughput(Throughput::Elements(size));
         group.bench_with_input(BenchmarkId::new("Feap", size), &size, |b, &size| {
             b.iter(|| {
-                let mut heap = Feap::new();
+                let mut heap: Feap<u64> = Feap::new();
                 (0..size).for_each(|i| heap.insert(black_box(NodePtr::new(i))));
             });
         });
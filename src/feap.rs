@@ -1,48 +1,62 @@
-use std::{collections::HashMap, ptr::NonNull};
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
 
 pub trait Item {
     type K: Ord;
+    type V;
 
-    fn new(key: Self::K) -> Self;
+    fn new(key: Self::K) -> Self
+    where
+        Self::V: Default;
     fn key(&self) -> &Self::K;
 }
 pub trait Heap {
     type Item: Item;
 
     fn new() -> Self;
-    fn find_min(&self) -> Option<&Self::Item>;
+    fn find_min(&self) -> Option<&<Self::Item as Item>::K>;
     fn insert(&mut self, node: Self::Item);
-    fn delete_min(&mut self) -> Option<Self::Item>;
+    fn delete_min(&mut self) -> Option<<Self::Item as Item>::K>;
     fn meld(self, other: Self) -> Self;
     fn decrease_key(&mut self, node: Self::Item, new_key: <Self::Item as Item>::K);
     fn delete(&mut self, node: Self::Item);
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct NodePtr<K: Ord>(NonNull<Node<K>>);
-impl<K: Ord> NodePtr<K> {
-    pub fn inner_ref(&self) -> &Node<K> {
+#[derive(Debug)]
+pub struct NodePtr<K: Ord, V = ()>(NonNull<Node<K, V>>);
+impl<K: Ord, V> PartialEq for NodePtr<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl<K: Ord, V> Eq for NodePtr<K, V> {}
+impl<K: Ord, V> NodePtr<K, V> {
+    pub fn inner_ref(&self) -> &Node<K, V> {
         unsafe { self.0.as_ref() }
     }
-    pub fn inner_mut(&mut self) -> &mut Node<K> {
+    pub fn inner_mut(&mut self) -> &mut Node<K, V> {
         unsafe { self.0.as_mut() }
     }
-    pub fn inner_ptr(&self) -> *mut Node<K> {
+    pub fn inner_ptr(&self) -> *mut Node<K, V> {
         self.0.as_ptr()
     }
-}
-impl<K: Ord> Copy for NodePtr<K> {}
-impl<K: Ord> Clone for NodePtr<K> {
-    fn clone(&self) -> Self {
-        *self
+
+    pub fn value(&self) -> &V {
+        &self.inner_ref().value
+    }
+    pub fn value_mut(&mut self) -> &mut V {
+        &mut self.inner_mut().value
     }
-}
-impl<K: Ord> Item for NodePtr<K> {
-    type K = K;
 
-    fn new(key: Self::K) -> Self {
+    /// Builds a node carrying `value` alongside its ordering key, without
+    /// requiring `V: Default` the way [`Item::new`] does.
+    pub fn new_with(key: K, value: V) -> Self {
         let node = Node {
             key,
+            value,
             rank: 0,
             is_marked: false,
             parent: None,
@@ -53,17 +67,40 @@ impl<K: Ord> Item for NodePtr<K> {
         unsafe { Self(NonNull::new_unchecked(Box::into_raw(Box::new(node)))) }
     }
 
+    /// Frees the node and moves its key out. The caller must guarantee the
+    /// node is no longer reachable from any heap.
+    pub(crate) unsafe fn into_key(self) -> K {
+        unsafe { Box::from_raw(self.inner_ptr()).key }
+    }
+}
+impl<K: Ord, V> Copy for NodePtr<K, V> {}
+impl<K: Ord, V> Clone for NodePtr<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<K: Ord, V> Item for NodePtr<K, V> {
+    type K = K;
+    type V = V;
+
+    fn new(key: Self::K) -> Self
+    where
+        V: Default,
+    {
+        Self::new_with(key, V::default())
+    }
+
     fn key(&self) -> &Self::K {
         &self.inner_ref().key
     }
 }
 
 #[derive(Debug, Default)]
-pub struct Feap<K: Ord> {
-    root: Option<NodePtr<K>>,
+pub struct Feap<K: Ord, V = ()> {
+    root: Option<NodePtr<K, V>>,
     len: usize,
 }
-impl<K: Ord> Feap<K> {
+impl<K: Ord, V> Feap<K, V> {
     pub fn len(&self) -> usize {
         self.len
     }
@@ -73,16 +110,58 @@ impl<K: Ord> Feap<K> {
     pub fn clear(&mut self) {
         *self = Self::new();
     }
+
+    /// Builds a node from `key` and `value` and inserts it in one step.
+    pub fn insert_with(&mut self, key: K, value: V) {
+        self.insert(NodePtr::new_with(key, value));
+    }
+
+    /// Returns a guard over the current minimum that re-validates the heap
+    /// on drop if its key was mutated through the guard.
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, K, V>> {
+        self.root?;
+        Some(PeekMut {
+            feap: self,
+            dirty: false,
+        })
+    }
+
+    /// Pops the minimum node and consolidates its children, handing the
+    /// node itself back to the caller instead of freeing it. This backs the
+    /// safe, owning [`Heap::delete_min`] as well as the keyed handle API,
+    /// which still needs the raw pointer to map a popped node back to its id.
+    pub(crate) fn take_min(&mut self) -> Option<NodePtr<K, V>> {
+        let root = self.root.take()?;
+        if root.inner_ref().first_child.is_some() {
+            self.root = consolidate(root.inner_ref().children());
+        }
+        self.len -= 1;
+        Some(root)
+    }
+
+    /// Returns the current minimum's key and value, without removing it.
+    /// The value-carrying counterpart to [`Heap::find_min`].
+    pub fn find_min_entry(&self) -> Option<(&K, &V)> {
+        self.root.as_ref().map(|root| (root.key(), root.value()))
+    }
+
+    /// Pops the minimum and hands back both its key and its value, freeing
+    /// the node. The value-carrying counterpart to [`Heap::delete_min`].
+    pub fn delete_min_entry(&mut self) -> Option<(K, V)> {
+        let node = self.take_min()?;
+        let boxed = unsafe { Box::from_raw(node.inner_ptr()) };
+        Some((boxed.key, boxed.value))
+    }
 }
-impl<K: Ord> Heap for Feap<K> {
-    type Item = NodePtr<K>;
+impl<K: Ord, V> Heap for Feap<K, V> {
+    type Item = NodePtr<K, V>;
 
     fn new() -> Self {
         Self { root: None, len: 0 }
     }
 
-    fn find_min(&self) -> Option<&Self::Item> {
-        self.root.as_ref()
+    fn find_min(&self) -> Option<&K> {
+        self.root.as_ref().map(|root| root.key())
     }
 
     fn insert(&mut self, node: Self::Item) {
@@ -91,24 +170,9 @@ impl<K: Ord> Heap for Feap<K> {
         self.len += 1;
     }
 
-    fn delete_min(&mut self) -> Option<Self::Item> {
-        if let Some(root) = self.root {
-            self.root = None;
-            if root.inner_ref().first_child.is_some() {
-                let mut rank_to_node = HashMap::new();
-                for mut node in root.inner_ref().children() {
-                    while let Some(other) = rank_to_node.remove(&node.inner_ref().rank) {
-                        node = fair_link(node, other);
-                    }
-                    rank_to_node.insert(node.inner_ref().rank, node);
-                }
-
-                self.root = rank_to_node.into_values().reduce(naive_link);
-            }
-            self.len -= 1;
-            return Some(root);
-        }
-        None
+    fn delete_min(&mut self) -> Option<K> {
+        let node = self.take_min()?;
+        Some(unsafe { node.into_key() })
     }
 
     fn meld(mut self, mut other: Self) -> Self {
@@ -139,12 +203,37 @@ impl<K: Ord> Heap for Feap<K> {
     }
 
     fn delete(&mut self, node: Self::Item) {
-        todo!()
+        let Some(mut root) = self.root else {
+            return;
+        };
+        if root == node {
+            self.delete_min();
+            return;
+        }
+
+        assert!(
+            node.inner_ref().parent.is_some(),
+            "a non-root node must have a parent"
+        );
+        root.inner_mut().is_marked = false;
+        decrease_ranks(node);
+        let node = unlink(node);
+
+        let mut new_root = root;
+        for child in node.inner_ref().children() {
+            new_root = naive_link(new_root, child);
+        }
+        self.root = Some(new_root);
+        self.len -= 1;
+
+        unsafe {
+            drop(Box::from_raw(node.inner_ptr()));
+        }
     }
 }
-impl<K: Ord> Drop for Feap<K> {
+impl<K: Ord, V> Drop for Feap<K, V> {
     fn drop(&mut self) {
-        fn rec_drop<K: Ord>(node: NodePtr<K>) {
+        fn rec_drop<K: Ord, V>(node: NodePtr<K, V>) {
             unsafe {
                 let children = node.inner_ref().children().collect::<Vec<_>>();
                 for c in children {
@@ -159,18 +248,101 @@ impl<K: Ord> Drop for Feap<K> {
     }
 }
 
+/// RAII guard returned by [`Feap::peek_mut`]. Derefs to the current
+/// minimum's key; if the key is mutated through the guard, the heap is
+/// re-validated on drop.
+#[derive(Debug)]
+pub struct PeekMut<'a, K: Ord, V = ()> {
+    feap: &'a mut Feap<K, V>,
+    dirty: bool,
+}
+impl<K: Ord, V> Deref for PeekMut<'_, K, V> {
+    type Target = K;
+
+    fn deref(&self) -> &K {
+        self.feap.root.as_ref().expect("guard holds a live root").key()
+    }
+}
+impl<K: Ord, V> DerefMut for PeekMut<'_, K, V> {
+    fn deref_mut(&mut self) -> &mut K {
+        self.dirty = true;
+        &mut self
+            .feap
+            .root
+            .as_mut()
+            .expect("guard holds a live root")
+            .inner_mut()
+            .key
+    }
+}
+impl<K: Ord, V> Drop for PeekMut<'_, K, V> {
+    fn drop(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        let mut node = self.feap.take_min().expect("guard holds a live root");
+        node.inner_mut().first_child = None;
+        node.inner_mut().rank = 0;
+        node.inner_mut().is_marked = false;
+        self.feap.insert(node);
+    }
+}
+
+impl<K: Ord, V> Feap<K, V> {
+    /// Drains the heap into a `Vec` of keys, ascending.
+    pub fn into_sorted_vec(self) -> Vec<K> {
+        self.into_iter().collect()
+    }
+}
+
+/// Owning iterator over a [`Feap`]'s keys, ascending. Built by repeatedly
+/// popping the minimum, so it costs `O(n log n)` overall.
+#[derive(Debug)]
+pub struct IntoIter<K: Ord, V = ()>(Feap<K, V>);
+impl<K: Ord, V> Iterator for IntoIter<K, V> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.delete_min()
+    }
+}
+impl<K: Ord, V> IntoIterator for Feap<K, V> {
+    type Item = K;
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<K: Ord, V: Default> FromIterator<K> for Feap<K, V> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut feap = Self::new();
+        feap.extend(iter);
+        feap
+    }
+}
+impl<K: Ord, V: Default> Extend<K> for Feap<K, V> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.insert(NodePtr::new(key));
+        }
+    }
+}
+
 #[derive(Debug, Default)]
-pub struct Node<K: Ord> {
+pub struct Node<K: Ord, V = ()> {
     key: K,
+    value: V,
     rank: u32,
     is_marked: bool,
-    parent: Option<NodePtr<K>>,
-    first_child: Option<NodePtr<K>>,
-    prev: Option<NodePtr<K>>,
-    next: Option<NodePtr<K>>,
+    parent: Option<NodePtr<K, V>>,
+    first_child: Option<NodePtr<K, V>>,
+    prev: Option<NodePtr<K, V>>,
+    next: Option<NodePtr<K, V>>,
 }
-impl<K: Ord> Node<K> {
-    pub fn children(&self) -> NodeChildrenIterator<K> {
+impl<K: Ord, V> Node<K, V> {
+    pub fn children(&self) -> NodeChildrenIterator<K, V> {
         NodeChildrenIterator {
             curr: self.first_child,
         }
@@ -178,11 +350,11 @@ impl<K: Ord> Node<K> {
 }
 
 #[derive(Debug)]
-pub struct NodeChildrenIterator<K: Ord> {
-    curr: Option<NodePtr<K>>,
+pub struct NodeChildrenIterator<K: Ord, V = ()> {
+    curr: Option<NodePtr<K, V>>,
 }
-impl<K: Ord> Iterator for NodeChildrenIterator<K> {
-    type Item = NodePtr<K>;
+impl<K: Ord, V> Iterator for NodeChildrenIterator<K, V> {
+    type Item = NodePtr<K, V>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(curr) = self.curr {
@@ -193,7 +365,7 @@ impl<K: Ord> Iterator for NodeChildrenIterator<K> {
     }
 }
 
-fn unlink<K: Ord>(this: NodePtr<K>) -> NodePtr<K> {
+fn unlink<K: Ord, V>(this: NodePtr<K, V>) -> NodePtr<K, V> {
     if let Some(mut parent) = this.inner_ref().parent {
         if parent.inner_ref().first_child == Some(this) {
             parent.inner_mut().first_child = this.inner_ref().next;
@@ -207,20 +379,20 @@ fn unlink<K: Ord>(this: NodePtr<K>) -> NodePtr<K> {
     }
     this
 }
-fn naive_link<K: Ord>(this: NodePtr<K>, other: NodePtr<K>) -> NodePtr<K> {
+fn naive_link<K: Ord, V>(this: NodePtr<K, V>, other: NodePtr<K, V>) -> NodePtr<K, V> {
     if this.key() < other.key() {
         add_child(this, other)
     } else {
         add_child(other, this)
     }
 }
-fn fair_link<K: Ord>(this: NodePtr<K>, other: NodePtr<K>) -> NodePtr<K> {
+fn fair_link<K: Ord, V>(this: NodePtr<K, V>, other: NodePtr<K, V>) -> NodePtr<K, V> {
     assert_eq!(this.inner_ref().rank, other.inner_ref().rank);
     let mut node = naive_link(this, other);
     node.inner_mut().rank += 1;
     node
 }
-fn add_child<K: Ord>(mut this: NodePtr<K>, mut other: NodePtr<K>) -> NodePtr<K> {
+fn add_child<K: Ord, V>(mut this: NodePtr<K, V>, mut other: NodePtr<K, V>) -> NodePtr<K, V> {
     other.inner_mut().parent = Some(this);
     other.inner_mut().prev = None;
     other.inner_mut().next = None;
@@ -231,7 +403,20 @@ fn add_child<K: Ord>(mut this: NodePtr<K>, mut other: NodePtr<K>) -> NodePtr<K>
     this.inner_mut().first_child = Some(other);
     this
 }
-fn decrease_ranks<K: Ord>(mut node: NodePtr<K>) {
+/// Pairs up equal-rank siblings via [`fair_link`] until ranks are unique,
+/// then folds the survivors into a single tree, preserving the min invariant.
+fn consolidate<K: Ord, V>(children: NodeChildrenIterator<K, V>) -> Option<NodePtr<K, V>> {
+    let mut rank_to_node = HashMap::new();
+    for mut node in children {
+        while let Some(other) = rank_to_node.remove(&node.inner_ref().rank) {
+            node = fair_link(node, other);
+        }
+        rank_to_node.insert(node.inner_ref().rank, node);
+    }
+    rank_to_node.into_values().reduce(naive_link)
+}
+
+fn decrease_ranks<K: Ord, V>(mut node: NodePtr<K, V>) {
     loop {
         let Some(parent) = node.inner_ref().parent else {
             break;
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::feap::Feap;
+use crate::feap::Heap;
+use crate::feap::Item;
+use crate::feap::Node;
+use crate::feap::NodePtr;
+
+/// A priority queue keyed by an external, hashable id, so callers can
+/// `decrease_key`/`delete` by id instead of juggling raw [`NodePtr`]s.
+#[derive(Debug, Default)]
+pub struct FeapMap<I: Hash + Eq, K: Ord> {
+    feap: Feap<K>,
+    handles: HashMap<I, NodePtr<K>>,
+    ids: HashMap<*mut Node<K>, I>,
+}
+impl<I: Hash + Eq + Clone, K: Ord> FeapMap<I, K> {
+    pub fn new() -> Self {
+        Self {
+            feap: Feap::new(),
+            handles: HashMap::new(),
+            ids: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.feap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.feap.is_empty()
+    }
+
+    pub fn get_priority(&self, id: &I) -> Option<&K> {
+        self.handles.get(id).map(|node| node.key())
+    }
+
+    pub fn insert(&mut self, id: I, key: K) {
+        assert!(
+            !self.handles.contains_key(&id),
+            "id already present; use decrease_key to update it"
+        );
+        let node = NodePtr::new(key);
+        self.handles.insert(id.clone(), node);
+        self.ids.insert(node.inner_ptr(), id);
+        self.feap.insert(node);
+    }
+
+    pub fn decrease_key(&mut self, id: &I, new_key: K) {
+        let node = *self.handles.get(id).expect("unknown id");
+        self.feap.decrease_key(node, new_key);
+    }
+
+    pub fn delete(&mut self, id: &I) {
+        let Some(node) = self.handles.remove(id) else {
+            return;
+        };
+        self.ids.remove(&node.inner_ptr());
+        self.feap.delete(node);
+    }
+
+    pub fn delete_min(&mut self) -> Option<(I, K)> {
+        let node = self.feap.take_min()?;
+        let id = self
+            .ids
+            .remove(&node.inner_ptr())
+            .expect("every live node has a tracked id");
+        self.handles.remove(&id);
+        let key = unsafe { node.into_key() };
+        Some((id, key))
+    }
+}
@@ -6,4 +6,6 @@
 
 /// Module containing the heap implementation
 pub mod feap;
+/// Module containing the keyed handle map built on top of [`feap`]
+pub mod feap_map;
 mod tests;
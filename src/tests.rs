@@ -4,6 +4,7 @@ use crate::feap::Feap;
 use crate::feap::Heap;
 use crate::feap::Item;
 use crate::feap::NodePtr;
+use crate::feap_map::FeapMap;
 
 #[cfg(test)]
 fn make_heap(start: i32, end: i32) -> Feap<i32> {
@@ -23,15 +24,15 @@ fn feap_empty() {
 #[test]
 fn feap_find_min() {
     let mut feap = make_heap(1, 3);
-    assert!(*feap.find_min().unwrap().key() == 1);
+    assert!(*feap.find_min().unwrap() == 1);
 
     feap.insert(NodePtr::new(0));
-    assert!(*feap.find_min().unwrap().key() == 0);
-    assert!(*feap.delete_min().unwrap().key() == 0);
-    assert!(*feap.find_min().unwrap().key() == 1);
+    assert!(*feap.find_min().unwrap() == 0);
+    assert!(feap.delete_min().unwrap() == 0);
+    assert!(*feap.find_min().unwrap() == 1);
 
     feap.insert(NodePtr::new(0));
-    assert!(*feap.find_min().unwrap().key() == 0);
+    assert!(*feap.find_min().unwrap() == 0);
 }
 
 #[test]
@@ -53,7 +54,7 @@ fn feap_merge() {
 
     let feap = feap1.meld(feap2);
     assert!(feap.len() == 20);
-    assert!(*feap.find_min().unwrap().key() == 0);
+    assert!(*feap.find_min().unwrap() == 0);
 }
 
 #[test]
@@ -62,13 +63,186 @@ fn feap_delete_min() {
     let mut len = feap.len();
 
     while len != 0 {
-        let min1 = *feap.find_min().unwrap().key();
-        let min2 = *feap.find_min().unwrap().key();
+        let min1 = *feap.find_min().unwrap();
+        let min2 = *feap.find_min().unwrap();
         assert!(min1 == min2);
 
-        let min = *feap.delete_min().unwrap().key();
+        let min = feap.delete_min().unwrap();
         assert_eq!(min, min1);
         assert_eq!(min, min2);
         len = feap.len();
     }
 }
+
+#[test]
+fn feap_delete_root() {
+    let mut feap: Feap<i32> = Feap::new();
+    let root = NodePtr::new(0);
+    feap.insert(root);
+    for i in 1..10 {
+        feap.insert(NodePtr::new(i));
+    }
+    let len = feap.len();
+
+    feap.delete(root);
+    assert_eq!(feap.len(), len - 1);
+    assert!(*feap.find_min().unwrap() == 1);
+}
+
+#[test]
+fn feap_delete_leaf_and_interior() {
+    let mut feap = Feap::new();
+    let nodes: Vec<NodePtr<i32>> = (0..20)
+        .map(|i| {
+            let node = NodePtr::new(i);
+            feap.insert(node);
+            node
+        })
+        .collect();
+
+    // force some consolidation so the tree has interior nodes with children
+    assert!(feap.delete_min().unwrap() == 0);
+
+    // delete everything above the minimum first, so these deletes hit
+    // non-root leaf and interior nodes, and the minimum itself last
+    let mut len = feap.len();
+    for &node in nodes[2..].iter().chain(&nodes[1..2]) {
+        feap.delete(node);
+        len -= 1;
+        assert_eq!(feap.len(), len);
+    }
+    assert!(feap.is_empty());
+    assert!(feap.find_min().is_none());
+}
+
+#[test]
+fn feap_delete_min_frees_every_node() {
+    use std::cmp::Ordering;
+    use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+    static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted(i32);
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            LIVE.fetch_sub(1, AtomicOrdering::SeqCst);
+        }
+    }
+    impl PartialEq for Counted {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+    impl Eq for Counted {}
+    impl PartialOrd for Counted {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Counted {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let mut feap: Feap<Counted> = Feap::new();
+    for i in 0..50 {
+        LIVE.fetch_add(1, AtomicOrdering::SeqCst);
+        feap.insert(NodePtr::new(Counted(i)));
+    }
+    assert_eq!(LIVE.load(AtomicOrdering::SeqCst), 50);
+
+    while feap.delete_min().is_some() {}
+    assert_eq!(LIVE.load(AtomicOrdering::SeqCst), 0);
+}
+
+#[test]
+fn feap_map_basic() {
+    let mut map = FeapMap::new();
+    for i in 0..10 {
+        map.insert(i, 10 - i);
+    }
+    assert_eq!(map.len(), 10);
+    assert_eq!(*map.get_priority(&9).unwrap(), 1);
+
+    let (id, key) = map.delete_min().unwrap();
+    assert_eq!(id, 9);
+    assert_eq!(key, 1);
+    assert_eq!(map.len(), 9);
+}
+
+#[test]
+#[should_panic(expected = "id already present")]
+fn feap_map_insert_rejects_duplicate_id() {
+    let mut map = FeapMap::new();
+    map.insert(1, 5);
+    map.insert(1, 3);
+}
+
+#[test]
+fn feap_value_payload() {
+    let mut feap: Feap<i32, &str> = Feap::new();
+    let one = NodePtr::new_with(1, "one");
+    feap.insert(one);
+    feap.insert_with(2, "two");
+    feap.insert_with(3, "three");
+
+    assert_eq!(*one.value(), "one");
+    assert_eq!(*feap.find_min().unwrap(), 1);
+
+    let (key, value) = feap.find_min_entry().unwrap();
+    assert_eq!(*key, 1);
+    assert_eq!(*value, "one");
+
+    assert_eq!(feap.delete_min_entry().unwrap(), (1, "one"));
+}
+
+#[test]
+fn feap_peek_mut() {
+    let mut feap = make_heap(0, 10);
+
+    {
+        let mut min = feap.peek_mut().unwrap();
+        assert_eq!(*min, 0);
+        *min = 5;
+    }
+    assert_eq!(feap.len(), 10);
+    assert!(*feap.find_min().unwrap() == 1);
+
+    // no mutation: the guard must leave the heap untouched on drop
+    {
+        let min = feap.peek_mut().unwrap();
+        assert_eq!(*min, 1);
+    }
+    assert!(*feap.find_min().unwrap() == 1);
+}
+
+#[test]
+fn feap_into_sorted_vec() {
+    let feap = make_heap(0, 10);
+    let sorted: Vec<i32> = feap.into_sorted_vec();
+    assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn feap_from_iterator_and_extend() {
+    let mut feap: Feap<i32> = vec![5, 3, 8, 1].into_iter().collect();
+    feap.extend(vec![0, 9]);
+    assert_eq!(feap.len(), 6);
+    assert_eq!(feap.into_sorted_vec(), vec![0, 1, 3, 5, 8, 9]);
+}
+
+#[test]
+fn feap_map_decrease_key_and_delete() {
+    let mut map = FeapMap::new();
+    for i in 0..10 {
+        map.insert(i, i);
+    }
+
+    map.decrease_key(&9, -1);
+    assert_eq!(map.delete_min().unwrap(), (9, -1));
+
+    map.delete(&5);
+    assert!(map.get_priority(&5).is_none());
+    assert_eq!(map.len(), 8);
+}